@@ -1,6 +1,33 @@
 /*!
 A derive proc macro that allow a struct to be read from a database using the mssql_client crate.
 
+A companion `#[derive(ToSql)]` is also provided to generate the write side (`sql_insert_str`
+and `sql_insert_params`) for a struct, using the same field attributes. The table name
+defaults to the pascal-cased struct name and can be overridden with `#[sql(table = "...")]`.
+
+Marking one or more fields with `#[sql(id)]` additionally generates `sql_update_str`/
+`sql_update_params` and `sql_delete_str`/`sql_delete_params`, using the marked fields as the
+`WHERE` clause. Structs with no `#[sql(id)]` field are insert-only and only get
+`sql_insert_str`/`sql_insert_params`.
+
+A struct can also load its query text from an external `.sql` file with
+`#[sql(file = "queries/my_row.sql")]`, which generates a `query_str()` method (with a
+`{fields}` placeholder filled in by `sql_fields_str()`) and a `bind(...)` method that takes
+one argument per named parameter (`:name`) found in the file and rewrites them to `@p1..@pN`.
+
+A field can be read through a custom decoder with `#[sql(with = "path::to_fn")]`, which calls
+`path::to_fn(idx, row)` instead of `row.get(idx)`.
+
+Column names default to the field name converted to pascal case, which can be changed struct-wide
+with `#[sql(rename_all = "...")]` (`snake_case`, `camelCase`, `PascalCase`, `SCREAMING_SNAKE`, or
+`verbatim`) and overridden per field with `#[sql(rename = "...")]`.
+
+An embedded struct's own fields can be flattened into the parent's columns with
+`#[sql(flatten)]`, which delegates to the nested type's `sql_fields_str()` and
+`from_row_offset()` instead of reading a single column. `#[sql(flatten)]` is read-only: a
+struct with a flattened field cannot also `#[derive(ToSql)]`, since the generated
+INSERT/UPDATE statements have no way to write the nested columns back.
+
 # Example
 ```
 use mssql_client::Connection;
@@ -55,7 +82,7 @@ use quote::quote;
 use syn::parse::{Parse, ParseStream, Result};
 use syn::{
     parenthesized, parse, parse_macro_input, Attribute, Data, DeriveInput, Error, Expr, Field,
-    Fields, Ident, LitInt, LitStr, Token,
+    Fields, Ident, LitStr, Token, Type,
 };
 
 #[proc_macro_derive(Sql, attributes(sql))]
@@ -69,51 +96,372 @@ pub fn sql(input: TokenStream) -> TokenStream {
         }
     };
 
-    let sql_fields = s.fields.iter().filter_map(|f| f.sql()).join(",");
     let name = s.name.clone();
 
-    let row_gets = s
-        .fields
-        .iter()
-        .filter_map(|f| match f {
-            SqlField::Expr { .. } => None,
-            SqlField::SqlNamed { ident, .. } | SqlField::SqlUnnamed(ident) => Some(ident),
-        })
-        .enumerate()
-        .map(|(index, ident)| {
+    let sql_field_parts = s.fields.iter().filter_map(|f| match f {
+        SqlField::Expr { .. } => None,
+        SqlField::Flatten { ty, .. } => Some(quote!(<#ty>::sql_fields_str())),
+        other => other.sql(s.casing).map(|lit| quote!(#lit.to_string())),
+    });
+
+    let field_lets = s.fields.iter().map(|f| match f {
+        SqlField::Expr { ident, expr } => quote!(let #ident = #expr;),
+        SqlField::Flatten { ident, ty } => quote! {
+            let (#ident, next_idx) = <#ty>::from_row_offset(idx, row)?;
+            idx = next_idx;
+        },
+        SqlField::With { ident, func, .. } => {
             let error = LitStr::new("Read `{}` failed; {}", ident.span());
             let field = LitStr::new(&ident.to_string(), ident.span());
-            let index = LitInt::new(&index.to_string(), ident.span());
-            quote!(#ident: row.get(#index).map_err(|e| failure::format_err!(#error, #field, e))?)
-        });
+            quote! {
+                let #ident = #func(idx, row).map_err(|e| failure::format_err!(#error, #field, e))?;
+                idx += 1;
+            }
+        }
+        SqlField::SqlNamed { ident, .. } | SqlField::SqlUnnamed { ident, .. } => {
+            let error = LitStr::new("Read `{}` failed; {}", ident.span());
+            let field = LitStr::new(&ident.to_string(), ident.span());
+            quote! {
+                let #ident = row.get(idx).map_err(|e| failure::format_err!(#error, #field, e))?;
+                idx += 1;
+            }
+        }
+    });
 
-    let expr_gets = s.fields.iter().filter_map(|f| match f {
-        SqlField::Expr { ident, expr } => Some(quote!(#ident: #expr)),
-        SqlField::SqlNamed { .. } | SqlField::SqlUnnamed(_) => None,
+    let field_idents = s.fields.iter().map(|f| match f {
+        SqlField::Expr { ident, .. }
+        | SqlField::Flatten { ident, .. }
+        | SqlField::With { ident, .. }
+        | SqlField::SqlNamed { ident, .. }
+        | SqlField::SqlUnnamed { ident, .. } => ident,
     });
 
+    let file_methods = match &s.file {
+        Some(file) => match query_file_methods(file) {
+            Ok(methods) => methods,
+            Err(e) => return e.to_compile_error().into(),
+        },
+        None => quote!(),
+    };
+
     quote!(
         impl #name {
-            pub(crate) fn sql_fields_str() -> &'static str {
-                #sql_fields
+            pub(crate) fn sql_fields_str() -> String {
+                [#(#sql_field_parts),*].join(",")
+            }
+
+            pub(crate) fn from_row_offset(
+                offset: usize,
+                row: &mssql_client::Row,
+            ) -> Result<(Self, usize), failure::Error> {
+                let mut idx = offset;
+                #(#field_lets)*
+                Ok((Self { #(#field_idents,)* }, idx))
             }
+
+            #file_methods
         }
 
         impl mssql_client::FromRow for #name {
             fn from_row(row: &mssql_client::Row) -> Result<Self, failure::Error> {
-                Ok(Self {
-                    #(#row_gets,)*
-                    #(#expr_gets,)*
-                })
+                Self::from_row_offset(0, row).map(|(value, _)| value)
             }
         }
     )
     .into()
 }
 
+fn query_file_methods(file: &LitStr) -> Result<proc_macro2::TokenStream> {
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let path = std::path::Path::new(&manifest_dir).join(file.value());
+
+    let contents = std::fs::read_to_string(&path).map_err(|e| {
+        Error::new(
+            file.span(),
+            format!("Failed to read sql file `{}`: {}", path.display(), e),
+        )
+    })?;
+
+    let (template, names) = rewrite_named_params(&contents);
+
+    let arg_idents: Vec<Ident> = names
+        .iter()
+        .map(|name| {
+            bind_arg_ident(name, file.span()).map_err(|_| {
+                Error::new(
+                    file.span(),
+                    format!(
+                        "Named parameter `:{}` in `{}` is a Rust keyword and can't be used as a `bind` argument name",
+                        name,
+                        path.display()
+                    ),
+                )
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let generics: Vec<Ident> = (0..names.len())
+        .map(|index| Ident::new(&format!("T{}", index), file.span()))
+        .collect();
+
+    Ok(quote! {
+        pub(crate) fn query_str() -> String {
+            #template.replacen("{fields}", &Self::sql_fields_str(), 1)
+        }
+
+        pub(crate) fn bind<#(#generics: mssql_client::ToSql,)*>(
+            #(#arg_idents: #generics,)*
+        ) -> (String, (#(#generics,)*)) {
+            (Self::query_str(), (#(#arg_idents,)*))
+        }
+    })
+}
+
+fn bind_arg_ident(name: &str, span: proc_macro2::Span) -> Result<Ident> {
+    let mut ident = syn::parse_str::<Ident>(name)?;
+    ident.set_span(span);
+    Ok(ident)
+}
+
+fn rewrite_named_params(text: &str) -> (String, Vec<String>) {
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::with_capacity(text.len());
+    let mut names: Vec<String> = Vec::new();
+    let mut i = 0;
+    let mut in_string = false;
+
+    while i < chars.len() {
+        let c = chars[i];
+
+        if !in_string && c == '-' && chars.get(i + 1) == Some(&'-') {
+            let start = i;
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+            out.extend(&chars[start..i]);
+            continue;
+        }
+
+        if !in_string && c == '/' && chars.get(i + 1) == Some(&'*') {
+            let start = i;
+            i += 2;
+            while i + 1 < chars.len() && !(chars[i] == '*' && chars[i + 1] == '/') {
+                i += 1;
+            }
+            i = (i + 2).min(chars.len());
+            out.extend(&chars[start..i]);
+            continue;
+        }
+
+        if c == '\'' {
+            in_string = !in_string;
+            out.push(c);
+            i += 1;
+            continue;
+        }
+
+        if in_string {
+            out.push(c);
+            i += 1;
+            continue;
+        }
+
+        let is_named_param = c == ':'
+            && i + 1 < chars.len()
+            && (chars[i + 1].is_alphabetic() || chars[i + 1] == '_')
+            && (i == 0 || chars[i - 1] != ':');
+
+        if is_named_param {
+            let start = i + 1;
+            let mut end = start;
+
+            while end < chars.len() && (chars[end].is_alphanumeric() || chars[end] == '_') {
+                end += 1;
+            }
+
+            let name: String = chars[start..end].iter().collect();
+            let index = names.iter().position(|n| n == &name).unwrap_or_else(|| {
+                names.push(name.clone());
+                names.len() - 1
+            });
+
+            out.push_str(&format!("@p{}", index + 1));
+            i = end;
+        } else {
+            out.push(c);
+            i += 1;
+        }
+    }
+
+    (out, names)
+}
+
+#[proc_macro_derive(ToSql, attributes(sql))]
+pub fn to_sql(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+
+    let s = match Struct::from_derive_input(derive_input) {
+        Ok(s) => s,
+        Err(e) => {
+            return e.to_compile_error().into();
+        }
+    };
+
+    let name = s.name.clone();
+
+    if let Some(ident) = s.fields.iter().find_map(|f| match f {
+        SqlField::Flatten { ident, .. } => Some(ident),
+        _ => None,
+    }) {
+        return Error::new(
+            ident.span(),
+            "ToSql does not support `#[sql(flatten)]` fields; flattened columns would be \
+             silently omitted from the generated INSERT/UPDATE statements. Read the nested \
+             fields through `Sql` only, or inline them as plain `#[sql(...)]` fields instead.",
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    let insert_fields: Vec<_> = s
+        .fields
+        .iter()
+        .filter_map(|f| match f {
+            SqlField::Expr { .. } | SqlField::Flatten { .. } => None,
+            SqlField::SqlNamed { ident, ty, .. }
+            | SqlField::SqlUnnamed { ident, ty, .. }
+            | SqlField::With { ident, ty, .. } => Some((ident, ty)),
+        })
+        .collect();
+
+    let insert_columns = s
+        .fields
+        .iter()
+        .filter_map(|f| match f {
+            SqlField::Expr { .. } | SqlField::Flatten { .. } => None,
+            f => f.sql(s.casing),
+        })
+        .join(",");
+
+    let insert_params = (1..=insert_fields.len())
+        .map(|index| format!("@p{}", index))
+        .join(",");
+
+    let insert_sql = format!(
+        "INSERT INTO [{}] ({}) VALUES ({})",
+        s.table, insert_columns, insert_params
+    );
+
+    let insert_idents = insert_fields.iter().map(|(ident, _)| ident);
+    let insert_types = insert_fields.iter().map(|(_, ty)| ty);
+
+    let id_items: Vec<_> = s
+        .fields
+        .iter()
+        .filter(|f| f.is_id())
+        .filter_map(|f| match f {
+            SqlField::SqlNamed { ident, ty, .. }
+            | SqlField::SqlUnnamed { ident, ty, .. }
+            | SqlField::With { ident, ty, .. } => Some((ident, ty, f.sql(s.casing).unwrap())),
+            SqlField::Expr { .. } | SqlField::Flatten { .. } => None,
+        })
+        .collect();
+
+    // Structs with no `#[sql(id)]` field are insert-only (e.g. an append-only log row); they
+    // keep `sql_insert_str`/`sql_insert_params` and simply don't get update/delete methods.
+    let crud_methods = if id_items.is_empty() {
+        quote!()
+    } else {
+        let data_items: Vec<_> = s
+            .fields
+            .iter()
+            .filter(|f| !f.is_id())
+            .filter_map(|f| match f {
+                SqlField::SqlNamed { ident, ty, .. }
+                | SqlField::SqlUnnamed { ident, ty, .. }
+                | SqlField::With { ident, ty, .. } => Some((ident, ty, f.sql(s.casing).unwrap())),
+                SqlField::Expr { .. } | SqlField::Flatten { .. } => None,
+            })
+            .collect();
+
+        let set_clause = data_items
+            .iter()
+            .enumerate()
+            .map(|(index, (_, _, col))| format!("{}=@p{}", col, index + 1))
+            .join(",");
+
+        let update_where_clause = id_items
+            .iter()
+            .enumerate()
+            .map(|(index, (_, _, col))| format!("{}=@p{}", col, data_items.len() + index + 1))
+            .join(" AND ");
+
+        let update_sql = format!(
+            "UPDATE [{}] SET {} WHERE {}",
+            s.table, set_clause, update_where_clause
+        );
+
+        let delete_where_clause = id_items
+            .iter()
+            .enumerate()
+            .map(|(index, (_, _, col))| format!("{}=@p{}", col, index + 1))
+            .join(" AND ");
+
+        let delete_sql = format!("DELETE FROM [{}] WHERE {}", s.table, delete_where_clause);
+
+        let update_idents = data_items
+            .iter()
+            .chain(id_items.iter())
+            .map(|(ident, _, _)| *ident);
+        let update_types = data_items
+            .iter()
+            .chain(id_items.iter())
+            .map(|(_, ty, _)| *ty);
+
+        let delete_idents = id_items.iter().map(|(ident, _, _)| *ident);
+        let delete_types = id_items.iter().map(|(_, ty, _)| *ty);
+
+        quote! {
+            pub(crate) fn sql_update_str() -> &'static str {
+                #update_sql
+            }
+
+            pub(crate) fn sql_update_params(&self) -> (#(&#update_types,)*) {
+                (#(&self.#update_idents,)*)
+            }
+
+            pub(crate) fn sql_delete_str() -> &'static str {
+                #delete_sql
+            }
+
+            pub(crate) fn sql_delete_params(&self) -> (#(&#delete_types,)*) {
+                (#(&self.#delete_idents,)*)
+            }
+        }
+    };
+
+    quote!(
+        impl #name {
+            pub(crate) fn sql_insert_str() -> &'static str {
+                #insert_sql
+            }
+
+            pub(crate) fn sql_insert_params(&self) -> (#(&#insert_types,)*) {
+                (#(&self.#insert_idents,)*)
+            }
+
+            #crud_methods
+        }
+    )
+    .into()
+}
+
 struct Struct {
     fields: Vec<SqlField>,
     name: Ident,
+    table: String,
+    casing: Casing,
+    file: Option<LitStr>,
 }
 
 impl Struct {
@@ -130,6 +478,20 @@ impl Struct {
             }
         };
 
+        let mut table = None;
+        let mut casing = Casing::PascalCase;
+        let mut file = None;
+
+        for attr in input.attrs.iter().filter_map(StructAttr::try_new) {
+            match attr? {
+                StructAttr::Table(name) => table = Some(name.value()),
+                StructAttr::RenameAll(name) => casing = Casing::from_lit(&name)?,
+                StructAttr::File(name) => file = Some(name),
+            }
+        }
+
+        let table = table.unwrap_or_else(|| name.to_string().to_pascal_case());
+
         let mut fields = Vec::new();
 
         match data_struct.fields {
@@ -146,14 +508,130 @@ impl Struct {
             }
         };
 
-        Ok(Struct { fields, name })
+        Ok(Struct {
+            fields,
+            name,
+            table,
+            casing,
+            file,
+        })
+    }
+}
+
+enum StructAttr {
+    Table(LitStr),
+    RenameAll(LitStr),
+    File(LitStr),
+}
+
+impl StructAttr {
+    fn try_new(a: &Attribute) -> Option<Result<Self>> {
+        let name = a
+            .path
+            .segments
+            .iter()
+            .map(|s| s.ident.to_string())
+            .join("::");
+        match name.as_str() {
+            "sql" | "sql_derive::sql" => Some(parse(a.tokens.clone().into())),
+            _ => None,
+        }
+    }
+}
+
+impl Parse for StructAttr {
+    fn parse(input: ParseStream) -> Result<StructAttr> {
+        let content;
+        let _ = parenthesized!(content in input);
+        let ident: Ident = content.parse()?;
+
+        let out = match ident.to_string().as_str() {
+            "table" => {
+                let _: Token![=] = content.parse()?;
+                StructAttr::Table(content.parse()?)
+            }
+            "rename_all" => {
+                let _: Token![=] = content.parse()?;
+                StructAttr::RenameAll(content.parse()?)
+            }
+            "file" => {
+                let _: Token![=] = content.parse()?;
+                StructAttr::File(content.parse()?)
+            }
+            _ => {
+                return Err(Error::new(
+                    ident.span(),
+                    "Expect `table`, `rename_all`, or `file`",
+                ))
+            }
+        };
+
+        Ok(out)
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Casing {
+    SnakeCase,
+    CamelCase,
+    PascalCase,
+    ScreamingSnake,
+    Verbatim,
+}
+
+impl Casing {
+    fn from_lit(lit: &LitStr) -> Result<Self> {
+        match lit.value().as_str() {
+            "snake_case" => Ok(Casing::SnakeCase),
+            "camelCase" => Ok(Casing::CamelCase),
+            "PascalCase" => Ok(Casing::PascalCase),
+            "SCREAMING_SNAKE" => Ok(Casing::ScreamingSnake),
+            "verbatim" => Ok(Casing::Verbatim),
+            _ => Err(Error::new(
+                lit.span(),
+                "Expect `snake_case`, `camelCase`, `PascalCase`, `SCREAMING_SNAKE`, or `verbatim`",
+            )),
+        }
+    }
+
+    fn apply(self, s: &str) -> String {
+        match self {
+            Casing::SnakeCase => s.to_snake_case(),
+            Casing::CamelCase => s.to_camel_case(),
+            Casing::PascalCase => s.to_pascal_case(),
+            Casing::ScreamingSnake => s.to_screaming_snake_case(),
+            Casing::Verbatim => s.to_string(),
+        }
     }
 }
 
 enum SqlField {
-    Expr { ident: Ident, expr: Expr },
-    SqlNamed { ident: Ident, name: LitStr },
-    SqlUnnamed(Ident),
+    Expr {
+        ident: Ident,
+        expr: Expr,
+    },
+    SqlNamed {
+        ident: Ident,
+        name: LitStr,
+        ty: Type,
+        id: bool,
+    },
+    SqlUnnamed {
+        ident: Ident,
+        ty: Type,
+        rename: Option<LitStr>,
+        id: bool,
+    },
+    With {
+        ident: Ident,
+        ty: Type,
+        func: Expr,
+        id: bool,
+    },
+    Flatten {
+        ident: Ident,
+        ty: Type,
+    },
 }
 
 impl SqlField {
@@ -164,25 +642,77 @@ impl SqlField {
             .expect("Sql struct field must be named.")
             .clone();
 
-        match f.attrs.iter().filter_map(SqlAttr::try_new).next() {
-            Some(Ok(SqlAttr::Expr(expr))) => Ok(SqlField::Expr { ident, expr }),
-            Some(Ok(SqlAttr::Default)) => Ok(SqlField::Expr {
+        let ty = f.ty.clone();
+
+        let mut id = false;
+        let mut kind = None;
+
+        for attr in f.attrs.iter().filter_map(SqlAttr::try_new) {
+            match attr? {
+                SqlAttr::Id => id = true,
+                attr => kind = Some(attr),
+            }
+        }
+
+        match kind {
+            Some(SqlAttr::Expr(expr)) => Ok(SqlField::Expr { ident, expr }),
+            Some(SqlAttr::Default) => Ok(SqlField::Expr {
                 ident,
                 expr: parse(quote!(Default::default()).into())?,
             }),
-            Some(Ok(SqlAttr::Name(name))) => Ok(SqlField::SqlNamed { ident, name }),
-            Some(Err(e)) => Err(e),
-            None => Ok(SqlField::SqlUnnamed(ident)),
+            Some(SqlAttr::Name(name)) => Ok(SqlField::SqlNamed {
+                ident,
+                name,
+                ty,
+                id,
+            }),
+            Some(SqlAttr::With(func)) => Ok(SqlField::With {
+                ident,
+                ty,
+                func,
+                id,
+            }),
+            Some(SqlAttr::Rename(name)) => Ok(SqlField::SqlUnnamed {
+                ident,
+                ty,
+                rename: Some(name),
+                id,
+            }),
+            Some(SqlAttr::Flatten) => Ok(SqlField::Flatten { ident, ty }),
+            Some(SqlAttr::Id) => unreachable!("id markers are consumed above"),
+            None => Ok(SqlField::SqlUnnamed {
+                ident,
+                ty,
+                rename: None,
+                id,
+            }),
         }
     }
 
-    fn sql(&self) -> Option<String> {
+    fn is_id(&self) -> bool {
+        match self {
+            SqlField::SqlNamed { id, .. }
+            | SqlField::SqlUnnamed { id, .. }
+            | SqlField::With { id, .. } => *id,
+            SqlField::Expr { .. } | SqlField::Flatten { .. } => false,
+        }
+    }
+
+    fn sql(&self, casing: Casing) -> Option<String> {
         match self {
             SqlField::Expr { .. } => None,
             SqlField::SqlNamed { name, .. } => Some(name.value()),
-            SqlField::SqlUnnamed(ident) => {
-                Some(format!("[{}]", ident.to_string().to_pascal_case()))
+            SqlField::SqlUnnamed { ident, rename, .. } => Some(format!(
+                "[{}]",
+                rename
+                    .as_ref()
+                    .map(|r| r.value())
+                    .unwrap_or_else(|| casing.apply(&ident.to_string()))
+            )),
+            SqlField::With { ident, .. } => {
+                Some(format!("[{}]", casing.apply(&ident.to_string())))
             }
+            SqlField::Flatten { .. } => None,
         }
     }
 }
@@ -191,6 +721,10 @@ enum SqlAttr {
     Default,
     Expr(Expr),
     Name(LitStr),
+    With(Expr),
+    Rename(LitStr),
+    Flatten,
+    Id,
 }
 
 impl SqlAttr {
@@ -228,10 +762,21 @@ impl Parse for SqlAttr {
                 let _: Token![=] = content.parse()?;
                 SqlAttr::Name(content.parse()?)
             }
+            "with" => {
+                let _: Token![=] = content.parse()?;
+                let path: LitStr = content.parse()?;
+                SqlAttr::With(path.parse()?)
+            }
+            "rename" => {
+                let _: Token![=] = content.parse()?;
+                SqlAttr::Rename(content.parse()?)
+            }
+            "flatten" => SqlAttr::Flatten,
+            "id" => SqlAttr::Id,
             _ => {
                 return Err(Error::new(
                     ident.span(),
-                    "Expect `default`, `expr`, or `name`",
+                    "Expect `default`, `expr`, `name`, `with`, `rename`, `flatten`, or `id`",
                 ))
             }
         };
@@ -239,3 +784,51 @@ impl Parse for SqlAttr {
         Ok(out)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{bind_arg_ident, rewrite_named_params};
+
+    #[test]
+    fn repeated_param_dedups_to_one_placeholder() {
+        let (template, names) = rewrite_named_params(":name, :name");
+        assert_eq!("@p1, @p1", template);
+        assert_eq!(vec!["name".to_string()], names);
+    }
+
+    #[test]
+    fn param_adjacent_to_punctuation_is_recognized() {
+        let (template, names) = rewrite_named_params("WHERE id = :id)");
+        assert_eq!("WHERE id = @p1)", template);
+        assert_eq!(vec!["id".to_string()], names);
+    }
+
+    #[test]
+    fn colon_inside_quoted_literal_is_not_a_param() {
+        let (template, names) = rewrite_named_params("WHERE t = '12:30'");
+        assert_eq!("WHERE t = '12:30'", template);
+        assert!(names.is_empty());
+    }
+
+    #[test]
+    fn colon_inside_comment_is_not_a_param() {
+        let (template, names) =
+            rewrite_named_params("-- formats time as HH:MM:SS\nSELECT :name");
+        assert_eq!("-- formats time as HH:MM:SS\nSELECT @p1", template);
+        assert_eq!(vec!["name".to_string()], names);
+    }
+
+    #[test]
+    fn colon_inside_block_comment_is_not_a_param() {
+        let (template, names) = rewrite_named_params("/* :not_a_param */ SELECT :name");
+        assert_eq!("/* :not_a_param */ SELECT @p1", template);
+        assert_eq!(vec!["name".to_string()], names);
+    }
+
+    #[test]
+    fn keyword_param_name_is_rejected() {
+        let (_, names) = rewrite_named_params("WHERE kind = :type");
+        assert_eq!(vec!["type".to_string()], names);
+        assert!(bind_arg_ident(&names[0], proc_macro2::Span::call_site()).is_err());
+    }
+}